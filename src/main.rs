@@ -0,0 +1,44 @@
+mod settings;
+
+use std::collections::HashMap;
+
+use criteria_policy_base::kubewarden_policy_sdk as kubewarden;
+use kubewarden::request::ValidationRequest;
+use kubewarden::settings::Validatable;
+use kubewarden::wapc_guest::prelude::*;
+use kubewarden::{accept_request, protocol_version_guest, reject_request, validate_settings};
+
+use settings::Settings;
+
+#[no_mangle]
+pub extern "C" fn wapc_init() {
+    register_function("validate", validate);
+    register_function("validate_settings", validate_settings::<Settings>);
+    register_function("protocol_version", protocol_version_guest);
+}
+
+fn validate(payload: &[u8]) -> CallResult {
+    let validation_request: ValidationRequest<Settings> = ValidationRequest::new(payload)?;
+    let annotations = extract_annotations(&validation_request.request.object);
+
+    match validation_request.settings.evaluate(&annotations) {
+        Ok(()) => accept_request(),
+        Err(message) => reject_request(Some(message), None, None, None),
+    }
+}
+
+/// Reads `metadata.annotations` off the admitted object, defaulting to empty
+/// when the resource has none.
+fn extract_annotations(object: &serde_json::Value) -> HashMap<String, String> {
+    object
+        .get("metadata")
+        .and_then(|metadata| metadata.get("annotations"))
+        .and_then(|annotations| annotations.as_object())
+        .map(|annotations| {
+            annotations
+                .iter()
+                .filter_map(|(key, value)| value.as_str().map(|value| (key.clone(), value.to_string())))
+                .collect()
+        })
+        .unwrap_or_default()
+}
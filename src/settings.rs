@@ -1,19 +1,144 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
+use std::str::FromStr;
+use std::sync::LazyLock;
 
 use criteria_policy_base::{kubewarden_policy_sdk as kubewarden, settings::BaseSettings};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 
+// A loose but practical check for `scheme://authority...` URLs, good enough to catch
+// the common misconfiguration of pasting a bare hostname or path into a URL field.
+// Compiled once since `ValueRule::check` runs on every admission request.
+static URL_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[a-zA-Z][a-zA-Z0-9+.-]*://[^\s]+$").unwrap());
+
+// A pragmatic email check (not a full RFC 5322 implementation), mirroring the level of
+// rigor the validator crate applies for this same rule. Compiled once, see above.
+static EMAIL_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[^\s@]+@[^\s@]+\.[^\s@]+$").unwrap());
+
+// Compiled once since `is_rfc_1123_label` runs on every admission request (once per
+// label of every RFC 1123 subdomain check, too).
+static RFC_1123_LABEL_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[a-z0-9]([-a-z0-9]*[a-z0-9])?$").unwrap());
+
+// Compiled once since `is_qualified_name_segment` runs on every admission request.
+static QUALIFIED_NAME_SEGMENT_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[A-Za-z0-9]([A-Za-z0-9_.-]*[A-Za-z0-9])?$").unwrap());
+
+/// A constraint that the *value* of a matched annotation must satisfy, on top of the
+/// key presence/absence rules already enforced by [`BaseSettings`].
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ValueRule {
+    /// The value must fully match this regular expression.
+    Regex(String),
+    /// The value must be one of the given strings.
+    OneOf(HashSet<String>),
+    /// The value's length (in chars) must fall within the given bounds, inclusive.
+    Length {
+        min: Option<usize>,
+        max: Option<usize>,
+    },
+    /// The value must be a well-formed URL.
+    Url,
+    /// The value must be a well-formed email address.
+    Email,
+    /// The value must be a well-formed IPv4 or IPv6 address.
+    Ip,
+    /// The value must not be empty.
+    NonEmpty,
+    /// The value must conform to the given Kubernetes syntactic class.
+    Format(ValueKind),
+}
+
+/// A Kubernetes syntactic class that an annotation value can be required to
+/// conform to, for policies gating values like `kubernetes.io/hostname`-style
+/// annotations where free-form strings are unacceptable.
 #[derive(Serialize, Deserialize, Debug, Clone)]
-pub(crate) struct Settings(pub(crate) BaseSettings);
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ValueKind {
+    /// An RFC 1123 label: at most 63 characters, lowercase alphanumeric or `-`,
+    /// must start and end with an alphanumeric character.
+    Rfc1123Label,
+    /// An RFC 1123 subdomain: dot-separated RFC 1123 labels, at most 253
+    /// characters in total.
+    Rfc1123Subdomain,
+    /// A Kubernetes qualified name: an optional RFC 1123 subdomain prefix
+    /// followed by `/` and a name segment of at most 63 characters.
+    QualifiedName,
+}
+
+impl ValueKind {
+    fn describe(&self) -> &'static str {
+        match self {
+            ValueKind::Rfc1123Label => "RFC 1123 label",
+            ValueKind::Rfc1123Subdomain => "RFC 1123 subdomain",
+            ValueKind::QualifiedName => "Kubernetes qualified name",
+        }
+    }
+}
+
+/// Checks `value` against RFC 1123's label format: at most 63 characters,
+/// lowercase alphanumeric or `-`, starting and ending with an alphanumeric
+/// character.
+fn is_rfc_1123_label(value: &str) -> bool {
+    if value.is_empty() || value.len() > 63 {
+        return false;
+    }
+    RFC_1123_LABEL_REGEX.is_match(value)
+}
+
+/// Checks `value` against RFC 1123's subdomain format: dot-separated RFC 1123
+/// labels, at most 253 characters in total.
+fn is_rfc_1123_subdomain(value: &str) -> bool {
+    if value.is_empty() || value.len() > 253 {
+        return false;
+    }
+    value.split('.').all(is_rfc_1123_label)
+}
+
+/// Checks `value` against Kubernetes' qualified name format: an optional RFC
+/// 1123 subdomain prefix followed by `/` and a name segment of at most 63
+/// characters (alphanumeric, `-`, `_`, `.`, starting/ending alphanumeric).
+fn is_qualified_name(value: &str) -> bool {
+    match value.split_once('/') {
+        Some((prefix, name)) => is_rfc_1123_subdomain(prefix) && is_qualified_name_segment(name),
+        None => is_qualified_name_segment(value),
+    }
+}
+
+fn is_qualified_name_segment(name: &str) -> bool {
+    if name.is_empty() || name.len() > 63 {
+        return false;
+    }
+    QUALIFIED_NAME_SEGMENT_REGEX.is_match(name)
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub(crate) struct Settings {
+    // Flattened so existing settings, which are serialized as a bare `BaseSettings`
+    // payload (e.g. `{"type":"ContainsAllOf","values":[...]}`), keep deserializing
+    // unchanged now that `rules` sits alongside it.
+    #[serde(flatten)]
+    pub(crate) base: BaseSettings,
+    /// Value constraints, keyed by annotation name. An annotation key that has no
+    /// entry here is only checked for presence/absence by `base`.
+    #[serde(default)]
+    pub(crate) rules: HashMap<String, Vec<ValueRule>>,
+}
 
 // It's not possible to use the Default in the derive macro because we cannot
 // set a #[default] attribute to enum item that is no unit enums.
 impl Default for Settings {
     fn default() -> Self {
-        Settings(BaseSettings::ContainsAnyOf {
-            values: HashSet::new(),
-        })
+        Settings {
+            base: BaseSettings::ContainsAnyOf {
+                values: HashSet::new(),
+            },
+            rules: HashMap::new(),
+        }
     }
 }
 
@@ -23,34 +148,447 @@ impl Default for Settings {
 // with the subdomain `/`escaped for a Rust literal
 const ANNOTATIONS_NAME_REGEX: &str = r"^([a-z0-9]([-a-z0-9]*[a-z0-9])?(\.[a-z0-9]([-a-z0-9]*[a-z0-9])?)*/)?[A-Za-z0-9]([A-Za-z0-9_.-]*[A-Za-z0-9])?$";
 
+/// Returns true if `entry` is a glob pattern (contains `*` or `?`) rather than a
+/// literal annotation name.
+fn is_glob_pattern(entry: &str) -> bool {
+    entry.contains('*') || entry.contains('?')
+}
+
+/// The two halves of an annotation name, each with its own legal character set.
+#[derive(Clone, Copy)]
+enum NameSegment {
+    /// The optional DNS-subdomain prefix before `/`: lowercase alphanumeric, `.`, `-`.
+    Prefix,
+    /// The key after (or in place of) the prefix: alphanumeric, `_`, `.`, `-`.
+    Key,
+}
+
+impl NameSegment {
+    fn char_class(self) -> &'static str {
+        match self {
+            NameSegment::Prefix => "a-z0-9.-",
+            NameSegment::Key => "A-Za-z0-9_.-",
+        }
+    }
+
+    fn allows(self, c: char) -> bool {
+        match self {
+            NameSegment::Prefix => c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '.' | '-'),
+            NameSegment::Key => c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'),
+        }
+    }
+}
+
+/// Translates a glob entry (e.g. `example.com/*` or `*/foo`) into an anchored,
+/// compiled [`Regex`], rejecting malformed globs with a descriptive error.
+///
+/// A glob token is accepted anywhere a literal label character is expected, but
+/// only expands within the legal character class of the segment it appears in:
+/// `*` becomes `[a-z0-9.-]*` in the prefix or `[A-Za-z0-9_.-]*` in the key, `?`
+/// the single-character equivalent. Neither ever matches `/`, so a pattern like
+/// `example.com/*` cannot accidentally swallow a nested `example.com/a/b/c`. Any
+/// literal (non-glob) character still has to belong to its segment's character
+/// class. The annotation-name shape (at most one `/`, no empty prefix/key
+/// segment) is enforced up front; `**` is rejected outright since it offers
+/// nothing `*` doesn't already provide and is a common copy-paste mistake.
+fn compile_annotation_name_pattern(entry: &str) -> Result<Regex, String> {
+    if entry.contains("**") {
+        return Err(format!(
+            "invalid annotation name pattern `{entry}`: `**` is not a supported glob"
+        ));
+    }
+    if entry.matches('/').count() > 1 {
+        return Err(format!(
+            "invalid annotation name pattern `{entry}`: at most one `/` is allowed"
+        ));
+    }
+    if entry.split('/').any(|segment| segment.is_empty()) {
+        return Err(format!(
+            "invalid annotation name pattern `{entry}`: prefix and key segments must not be empty"
+        ));
+    }
+
+    let mut pattern = String::from("^");
+    match entry.split_once('/') {
+        Some((prefix, key)) => {
+            pattern.push_str(&translate_name_segment(prefix, NameSegment::Prefix, entry)?);
+            pattern.push('/');
+            pattern.push_str(&translate_name_segment(key, NameSegment::Key, entry)?);
+        }
+        None => pattern.push_str(&translate_name_segment(entry, NameSegment::Key, entry)?),
+    }
+    pattern.push('$');
+
+    Regex::new(&pattern).map_err(|e| format!("invalid annotation name pattern `{entry}`: {e}"))
+}
+
+/// Translates a single prefix or key segment into its regex fragment, rejecting
+/// any literal character that falls outside the segment's legal character class.
+fn translate_name_segment(segment: &str, kind: NameSegment, entry: &str) -> Result<String, String> {
+    let mut fragment = String::new();
+    for c in segment.chars() {
+        match c {
+            '*' => fragment.push_str(&format!("[{}]*", kind.char_class())),
+            '?' => fragment.push_str(&format!("[{}]", kind.char_class())),
+            _ if kind.allows(c) => fragment.push_str(&regex::escape(&c.to_string())),
+            _ => {
+                return Err(format!(
+                    "invalid annotation name pattern `{entry}`: illegal character '{c}' in `{segment}`"
+                ));
+            }
+        }
+    }
+    Ok(fragment)
+}
+
+/// Computes a best-effort corrected name for an invalid annotation name, returning
+/// `None` when no repair brings it in line with [`ANNOTATIONS_NAME_REGEX`].
+fn suggest_annotation_name(annot: &str) -> Option<String> {
+    let suggestion = match annot.split_once('/') {
+        Some((prefix, key)) => format!("{}/{}", repair_dns_prefix(prefix), repair_annotation_key(key)),
+        None => repair_annotation_key(annot),
+    };
+
+    let annotations_name_regex = Regex::new(ANNOTATIONS_NAME_REGEX).unwrap();
+    if annotations_name_regex.is_match(&suggestion) {
+        Some(suggestion)
+    } else {
+        None
+    }
+}
+
+/// Repairs a DNS-subdomain prefix: lowercases it, replaces runs of illegal
+/// characters with `-`, collapses consecutive dots, and trims leading/trailing
+/// `-`/`.` from each label.
+fn repair_dns_prefix(prefix: &str) -> String {
+    let lowered = prefix.to_lowercase();
+    let illegal_run = Regex::new(r"[^a-z0-9.-]+").unwrap();
+    let no_illegal = illegal_run.replace_all(&lowered, "-");
+    let dot_run = Regex::new(r"\.{2,}").unwrap();
+    let collapsed_dots = dot_run.replace_all(&no_illegal, ".");
+
+    collapsed_dots
+        .split('.')
+        .map(|label| label.trim_matches(|c| c == '-' || c == '.'))
+        .filter(|label| !label.is_empty())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Repairs an annotation key: drops illegal interior characters, then trims
+/// leading/trailing characters that are not alphanumeric.
+fn repair_annotation_key(key: &str) -> String {
+    let illegal = Regex::new(r"[^A-Za-z0-9_.-]").unwrap();
+    let filtered = illegal.replace_all(key, "");
+    filtered
+        .trim_matches(|c: char| !c.is_ascii_alphanumeric())
+        .to_string()
+}
+
+impl ValueRule {
+    /// Compiles any regex carried by this rule, surfacing a descriptive error that
+    /// names the offending annotation key and pattern if compilation fails.
+    fn compile(&self, key: &str) -> Result<(), String> {
+        match self {
+            ValueRule::Regex(pattern) => Regex::new(pattern).map(|_| ()).map_err(|e| {
+                format!("invalid regex `{pattern}` for annotation key `{key}`: {e}")
+            }),
+            ValueRule::OneOf(values) => {
+                if values.iter().any(|v| v.is_empty()) {
+                    return Err(format!(
+                        "`one_of` rule for annotation key `{key}` contains an empty value"
+                    ));
+                }
+                Ok(())
+            }
+            ValueRule::Length { .. }
+            | ValueRule::Url
+            | ValueRule::Email
+            | ValueRule::Ip
+            | ValueRule::NonEmpty
+            | ValueRule::Format(_) => Ok(()),
+        }
+    }
+
+    /// Checks `value` against this rule, returning a description of the violated
+    /// constraint on failure. `regex_cache` should hold every `Regex`-kind rule's
+    /// pattern pre-compiled (see [`Settings::compile_value_regexes`]); a cache miss falls
+    /// back to compiling the pattern here so the check is still correct on its own.
+    fn check(&self, key: &str, value: &str, regex_cache: &HashMap<String, Regex>) -> Result<(), String> {
+        match self {
+            ValueRule::Regex(pattern) => {
+                let matched = match regex_cache.get(pattern) {
+                    Some(re) => re.is_match(value),
+                    None => {
+                        let anchored = format!("^(?:{pattern})$");
+                        Regex::new(&anchored)
+                            .map_err(|e| {
+                                format!("invalid regex `{pattern}` for annotation key `{key}`: {e}")
+                            })?
+                            .is_match(value)
+                    }
+                };
+                if matched {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "annotation `{key}` value `{value}` does not match regex `{pattern}`"
+                    ))
+                }
+            }
+            ValueRule::OneOf(values) => {
+                if values.contains(value) {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "annotation `{key}` value `{value}` is not one of the allowed values"
+                    ))
+                }
+            }
+            ValueRule::Length { min, max } => {
+                let len = value.chars().count();
+                if min.is_some_and(|min| len < min) || max.is_some_and(|max| len > max) {
+                    Err(format!(
+                        "annotation `{key}` value `{value}` has length {len}, outside the allowed bounds"
+                    ))
+                } else {
+                    Ok(())
+                }
+            }
+            ValueRule::Url => {
+                if URL_REGEX.is_match(value) {
+                    Ok(())
+                } else {
+                    Err(format!("annotation `{key}` value `{value}` is not a valid URL"))
+                }
+            }
+            ValueRule::Email => {
+                if EMAIL_REGEX.is_match(value) {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "annotation `{key}` value `{value}` is not a valid email address"
+                    ))
+                }
+            }
+            ValueRule::Ip => {
+                if IpAddr::from_str(value).is_ok() {
+                    Ok(())
+                } else {
+                    Err(format!("annotation `{key}` value `{value}` is not a valid IP address"))
+                }
+            }
+            ValueRule::NonEmpty => {
+                if value.is_empty() {
+                    Err(format!("annotation `{key}` value must not be empty"))
+                } else {
+                    Ok(())
+                }
+            }
+            ValueRule::Format(kind) => {
+                let is_valid = match kind {
+                    ValueKind::Rfc1123Label => is_rfc_1123_label(value),
+                    ValueKind::Rfc1123Subdomain => is_rfc_1123_subdomain(value),
+                    ValueKind::QualifiedName => is_qualified_name(value),
+                };
+                if is_valid {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "annotation `{key}` value `{value}` is not a valid {}",
+                        kind.describe()
+                    ))
+                }
+            }
+        }
+    }
+}
+
 impl kubewarden::settings::Validatable for Settings {
     fn validate(&self) -> Result<(), String> {
         // this will fail if the annotations key list is empty
-        self.0.validate()?;
+        self.base.validate()?;
 
-        let annots = self.0.values();
+        let annots = self.base.values();
+        let (glob_annot, literal_annot): (Vec<&String>, Vec<&String>) =
+            annots.iter().partition(|annot| is_glob_pattern(annot));
 
-        // Validate that the annotations names are valid.
+        // Validate that the literal annotations names are valid.
         let annotations_name_regex = Regex::new(ANNOTATIONS_NAME_REGEX).unwrap();
-        let invalid_annot: Vec<String> = annots
+        let invalid_annot: Vec<String> = literal_annot
             .iter()
             .filter_map(|annot| {
                 if annotations_name_regex.is_match(annot) {
                     return None;
                 }
-                Some(annot.to_string())
+                Some(match suggest_annotation_name(annot) {
+                    Some(suggestion) => {
+                        format!("invalid annotation name \"{annot}\"; did you mean \"{suggestion}\"?")
+                    }
+                    None => format!("invalid annotation name \"{annot}\""),
+                })
             })
             .collect();
         if !invalid_annot.is_empty() {
-            return Err(format!(
-                "Invalid annotation names: {}",
-                invalid_annot.join(", "),
-            ));
+            return Err(invalid_annot.join("; "));
+        }
+
+        // Validate that every glob annotation name compiles into a usable pattern.
+        let invalid_glob_annot: Vec<String> = glob_annot
+            .iter()
+            .filter_map(|annot| compile_annotation_name_pattern(annot).err())
+            .collect();
+        if !invalid_glob_annot.is_empty() {
+            return Err(invalid_glob_annot.join("; "));
+        }
+
+        // Compile every configured value rule once, surfacing any misconfiguration
+        // (bad regex, empty `one_of` entries) at settings-load time.
+        for (key, value_rules) in &self.rules {
+            for rule in value_rules {
+                rule.compile(key)?;
+            }
         }
+
         Ok(())
     }
 }
 
+/// Returns true if `key` satisfies the annotation name entry `entry`, expanding
+/// `entry` as a glob if it is one rather than doing exact string comparison.
+fn entry_matches(entry: &str, key: &str) -> bool {
+    if is_glob_pattern(entry) {
+        compile_annotation_name_pattern(entry)
+            .map(|re| re.is_match(key))
+            .unwrap_or(false)
+    } else {
+        entry == key
+    }
+}
+
+/// Pre-compiles every glob entry in `entries` once, pairing each entry with its
+/// compiled pattern (`None` for literal entries, which are compared by exact
+/// string equality). Use with [`compiled_entry_matches`] to avoid recompiling a
+/// glob once per present key it's tested against.
+fn compile_name_entries(entries: &HashSet<String>) -> Vec<(&String, Option<Regex>)> {
+    entries
+        .iter()
+        .map(|entry| {
+            let pattern = is_glob_pattern(entry)
+                .then(|| compile_annotation_name_pattern(entry).ok())
+                .flatten();
+            (entry, pattern)
+        })
+        .collect()
+}
+
+/// Tests `key` against a name entry pre-compiled by [`compile_name_entries`].
+fn compiled_entry_matches((entry, pattern): &(&String, Option<Regex>), key: &str) -> bool {
+    match pattern {
+        Some(re) => re.is_match(key),
+        None => entry.as_str() == key,
+    }
+}
+
+impl Settings {
+    /// Tests `key` against the configured annotation name entries, expanding any
+    /// glob entry (e.g. `example.com/*`) into its compiled pattern rather than
+    /// doing exact string membership.
+    pub(crate) fn matches_annotation_key(&self, key: &str) -> bool {
+        self.base.values().iter().any(|entry| entry_matches(entry, key))
+    }
+
+    /// Runs every runtime check configured in these settings against a resource's
+    /// annotations: key presence/absence (honoring glob entries), then, for any
+    /// annotation with configured value rules, those rules too.
+    pub(crate) fn evaluate(&self, annotations: &HashMap<String, String>) -> Result<(), String> {
+        self.evaluate_presence(annotations)?;
+        self.check_annotation_values(annotations)
+    }
+
+    /// Checks the resource's annotation keys against `base`'s presence/absence
+    /// requirement, honoring glob entries via [`compile_name_entries`] and
+    /// [`compiled_entry_matches`].
+    fn evaluate_presence(&self, annotations: &HashMap<String, String>) -> Result<(), String> {
+        let present_keys: Vec<&str> = annotations.keys().map(String::as_str).collect();
+        match &self.base {
+            BaseSettings::ContainsAnyOf { values } => {
+                let entries = compile_name_entries(values);
+                if entries
+                    .iter()
+                    .any(|entry| present_keys.iter().any(|key| compiled_entry_matches(entry, key)))
+                {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "resource annotations do not contain any of the required entries: {}",
+                        values.iter().cloned().collect::<Vec<_>>().join(", ")
+                    ))
+                }
+            }
+            BaseSettings::ContainsAllOf { values } => {
+                let entries = compile_name_entries(values);
+                let missing: Vec<&String> = entries
+                    .iter()
+                    .filter(|entry| !present_keys.iter().any(|key| compiled_entry_matches(entry, key)))
+                    .map(|(entry, _)| *entry)
+                    .collect();
+                if missing.is_empty() {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "resource annotations are missing required entries: {}",
+                        missing.iter().map(String::as_str).collect::<Vec<_>>().join(", ")
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Pre-compiles every [`ValueRule::Regex`] pattern configured in `rules` once,
+    /// keyed by pattern text, so [`ValueRule::check`] doesn't recompile the same
+    /// pattern on every annotation it's checked against.
+    fn compile_value_regexes(&self) -> HashMap<String, Regex> {
+        self.rules
+            .values()
+            .flatten()
+            .filter_map(|rule| match rule {
+                ValueRule::Regex(pattern) => {
+                    let anchored = format!("^(?:{pattern})$");
+                    Regex::new(&anchored).ok().map(|re| (pattern.clone(), re))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Checks the given resource annotations against the configured [`ValueRule`]s,
+    /// reporting every key/constraint violation found. Annotations without a
+    /// configured rule are not checked here; key presence/absence is `base`'s job.
+    pub(crate) fn check_annotation_values(
+        &self,
+        annotations: &HashMap<String, String>,
+    ) -> Result<(), String> {
+        let regex_cache = self.compile_value_regexes();
+        let mut violations = Vec::new();
+        for (key, value_rules) in &self.rules {
+            let Some(value) = annotations.get(key) else {
+                continue;
+            };
+            for rule in value_rules {
+                if let Err(e) = rule.check(key, value, &regex_cache) {
+                    violations.push(e);
+                }
+            }
+        }
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations.join("; "))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -78,12 +616,218 @@ mod tests {
     #[case::invalid_uppercase_prefix(vec!["Example.com/my-annotation"], false)]
     #[case::invalid_double_dot_prefix(vec!["example..com/my-annotation"], false)]
     fn test_validation(#[case] variables: Vec<&str>, #[case] is_ok: bool) {
-        let settings = Settings(BaseSettings::ContainsAllOf {
-            values: variables
-                .iter()
-                .map(|v| v.to_string())
-                .collect::<HashSet<String>>(),
-        });
+        let settings = Settings {
+            base: BaseSettings::ContainsAllOf {
+                values: variables
+                    .iter()
+                    .map(|v| v.to_string())
+                    .collect::<HashSet<String>>(),
+            },
+            rules: HashMap::new(),
+        };
+        assert_eq!(settings.validate().is_ok(), is_ok);
+    }
+
+    #[rstest]
+    #[case::regex_match("release-1.2.3", ValueRule::Regex(r"release-\d+\.\d+\.\d+".to_string()), true)]
+    #[case::regex_mismatch("v1", ValueRule::Regex(r"release-\d+\.\d+\.\d+".to_string()), false)]
+    #[case::one_of_match("prod", ValueRule::OneOf(HashSet::from(["prod".to_string(), "staging".to_string()])), true)]
+    #[case::one_of_mismatch("dev", ValueRule::OneOf(HashSet::from(["prod".to_string(), "staging".to_string()])), false)]
+    #[case::length_within_bounds("abc", ValueRule::Length { min: Some(1), max: Some(5) }, true)]
+    #[case::length_too_long("abcdef", ValueRule::Length { min: Some(1), max: Some(5) }, false)]
+    #[case::url_valid("https://example.com/path", ValueRule::Url, true)]
+    #[case::url_invalid("example.com/path", ValueRule::Url, false)]
+    #[case::email_valid("team@example.com", ValueRule::Email, true)]
+    #[case::email_invalid("not-an-email", ValueRule::Email, false)]
+    #[case::ip_valid("192.168.0.1", ValueRule::Ip, true)]
+    #[case::ip_invalid("not-an-ip", ValueRule::Ip, false)]
+    #[case::non_empty_valid("x", ValueRule::NonEmpty, true)]
+    #[case::non_empty_invalid("", ValueRule::NonEmpty, false)]
+    #[case::rfc_1123_label_valid("my-host", ValueRule::Format(ValueKind::Rfc1123Label), true)]
+    #[case::rfc_1123_label_invalid("My-Host", ValueRule::Format(ValueKind::Rfc1123Label), false)]
+    #[case::rfc_1123_subdomain_valid("my.host.example.com", ValueRule::Format(ValueKind::Rfc1123Subdomain), true)]
+    #[case::rfc_1123_subdomain_invalid("my..host", ValueRule::Format(ValueKind::Rfc1123Subdomain), false)]
+    #[case::qualified_name_valid("example.com/my-value", ValueRule::Format(ValueKind::QualifiedName), true)]
+    #[case::qualified_name_invalid("Example.com/my-value", ValueRule::Format(ValueKind::QualifiedName), false)]
+    fn test_value_rule_check(#[case] value: &str, #[case] rule: ValueRule, #[case] is_ok: bool) {
+        assert_eq!(rule.check("my-annotation", value, &HashMap::new()).is_ok(), is_ok);
+    }
+
+    #[rstest]
+    #[case::valid_short("a", true)]
+    #[case::valid_dashes("my-host-1", true)]
+    #[case::invalid_empty("", false)]
+    #[case::invalid_uppercase("My-Host", false)]
+    #[case::invalid_leading_dash("-my-host", false)]
+    #[case::invalid_trailing_dash("my-host-", false)]
+    #[case::invalid_too_long(&"a".repeat(64), false)]
+    fn test_is_rfc_1123_label(#[case] value: &str, #[case] expected: bool) {
+        assert_eq!(is_rfc_1123_label(value), expected);
+    }
+
+    #[rstest]
+    #[case::valid_single_label("my-host", true)]
+    #[case::valid_multi_label("my-host.example.com", true)]
+    #[case::invalid_empty_label("my-host..example.com", false)]
+    #[case::invalid_uppercase("My-Host.example.com", false)]
+    #[case::invalid_too_long(&format!("{}.com", "a".repeat(253)), false)]
+    fn test_is_rfc_1123_subdomain(#[case] value: &str, #[case] expected: bool) {
+        assert_eq!(is_rfc_1123_subdomain(value), expected);
+    }
+
+    #[rstest]
+    #[case::valid_no_prefix("my-annotation", true)]
+    #[case::valid_with_prefix("example.com/my-annotation", true)]
+    #[case::invalid_uppercase_prefix("Example.com/my-annotation", false)]
+    #[case::invalid_empty_name("example.com/", false)]
+    #[case::invalid_name_too_long(&format!("example.com/{}", "a".repeat(64)), false)]
+    fn test_is_qualified_name(#[case] value: &str, #[case] expected: bool) {
+        assert_eq!(is_qualified_name(value), expected);
+    }
+
+    #[test]
+    fn test_deserializes_pre_existing_bare_base_settings_payload() {
+        // Settings serialized by deployments predating `rules` must keep loading:
+        // the wire format is the bare `BaseSettings` payload, with no `rules` key.
+        let json = r#"{"type":"ContainsAllOf","values":["my-annotation"]}"#;
+        let settings: Settings = serde_json::from_str(json).expect("must deserialize");
+        assert!(settings.rules.is_empty());
+        assert!(settings
+            .base
+            .values()
+            .iter()
+            .any(|v| v == "my-annotation"));
+    }
+
+    #[test]
+    fn test_validate_rejects_invalid_regex() {
+        let settings = Settings {
+            base: BaseSettings::ContainsAllOf {
+                values: HashSet::from(["my-annotation".to_string()]),
+            },
+            rules: HashMap::from([(
+                "my-annotation".to_string(),
+                vec![ValueRule::Regex("[".to_string())],
+            )]),
+        };
+        assert!(settings.validate().is_err());
+    }
+
+    #[rstest]
+    #[case::prefix_wildcard("*/foo", true)]
+    #[case::key_wildcard("example.com/*", true)]
+    #[case::single_char_wildcard("example.com/my-annotation?", true)]
+    #[case::double_star("example.com/**", false)]
+    #[case::too_many_slashes("example.com/foo/*", false)]
+    #[case::empty_key_segment("*.example.com/", false)]
+    #[case::invalid_uppercase_prefix("Example.com/*", false)]
+    #[case::invalid_space_in_prefix("ex ample.com/*", false)]
+    #[case::invalid_at_symbol_in_key("example.com/my@*", false)]
+    fn test_glob_annotation_name_validation(#[case] pattern: &str, #[case] is_ok: bool) {
+        let settings = Settings {
+            base: BaseSettings::ContainsAllOf {
+                values: HashSet::from([pattern.to_string()]),
+            },
+            rules: HashMap::new(),
+        };
         assert_eq!(settings.validate().is_ok(), is_ok);
     }
+
+    #[rstest]
+    #[case::prefix_wildcard("*/foo", "mycompany.com/foo", true)]
+    #[case::prefix_wildcard_mismatch("*/foo", "mycompany.com/bar", false)]
+    #[case::key_wildcard("example.com/*", "example.com/anything", true)]
+    #[case::key_wildcard_mismatch("example.com/*", "other.com/anything", false)]
+    #[case::key_wildcard_does_not_cross_slash("example.com/*", "example.com/a/b/c", false)]
+    #[case::key_wildcard_rejects_space("example.com/*", "example.com/bad name", false)]
+    #[case::literal_match("my-annotation", "my-annotation", true)]
+    fn test_matches_annotation_key(
+        #[case] pattern: &str,
+        #[case] key: &str,
+        #[case] expected: bool,
+    ) {
+        let settings = Settings {
+            base: BaseSettings::ContainsAllOf {
+                values: HashSet::from([pattern.to_string()]),
+            },
+            rules: HashMap::new(),
+        };
+        assert_eq!(settings.matches_annotation_key(key), expected);
+    }
+
+    #[rstest]
+    #[case::fixable_example(
+        "Example.com/My_Annotation-",
+        Some("example.com/My_Annotation".to_string())
+    )]
+    #[case::fixable_illegal_prefix_chars("ex@mple.com/foo", Some("ex-mple.com/foo".to_string()))]
+    #[case::fixable_double_dot_prefix("example..com/foo", Some("example.com/foo".to_string()))]
+    #[case::fixable_no_prefix("-my_annotation-", Some("my_annotation".to_string()))]
+    #[case::unfixable_empty_key("example.com/---", None)]
+    fn test_suggest_annotation_name(#[case] annot: &str, #[case] expected: Option<String>) {
+        assert_eq!(suggest_annotation_name(annot), expected);
+    }
+
+    #[test]
+    fn test_validate_reports_suggestion_in_error() {
+        let settings = Settings {
+            base: BaseSettings::ContainsAllOf {
+                values: HashSet::from(["Example.com/My_Annotation-".to_string()]),
+            },
+            rules: HashMap::new(),
+        };
+        let err = settings.validate().unwrap_err();
+        assert!(err.contains("did you mean \"example.com/My_Annotation\""));
+    }
+
+    #[test]
+    fn test_check_annotation_values_reports_violations() {
+        let settings = Settings {
+            base: BaseSettings::ContainsAllOf {
+                values: HashSet::from(["env".to_string()]),
+            },
+            rules: HashMap::from([(
+                "env".to_string(),
+                vec![ValueRule::OneOf(HashSet::from([
+                    "prod".to_string(),
+                    "staging".to_string(),
+                ]))],
+            )]),
+        };
+        let annotations = HashMap::from([("env".to_string(), "dev".to_string())]);
+        assert!(settings.check_annotation_values(&annotations).is_err());
+    }
+
+    #[rstest]
+    #[case::all_of_satisfied_via_glob(
+        BaseSettings::ContainsAllOf { values: HashSet::from(["example.com/*".to_string()]) },
+        HashMap::from([("example.com/team".to_string(), "x".to_string())]),
+        true,
+    )]
+    #[case::all_of_missing(
+        BaseSettings::ContainsAllOf { values: HashSet::from(["example.com/team".to_string()]) },
+        HashMap::new(),
+        false,
+    )]
+    #[case::any_of_satisfied(
+        BaseSettings::ContainsAnyOf { values: HashSet::from(["a".to_string(), "b".to_string()]) },
+        HashMap::from([("b".to_string(), "x".to_string())]),
+        true,
+    )]
+    #[case::any_of_none_present(
+        BaseSettings::ContainsAnyOf { values: HashSet::from(["a".to_string(), "b".to_string()]) },
+        HashMap::new(),
+        false,
+    )]
+    fn test_evaluate_presence(
+        #[case] base: BaseSettings,
+        #[case] annotations: HashMap<String, String>,
+        #[case] expected_ok: bool,
+    ) {
+        let settings = Settings {
+            base,
+            rules: HashMap::new(),
+        };
+        assert_eq!(settings.evaluate(&annotations).is_ok(), expected_ok);
+    }
 }